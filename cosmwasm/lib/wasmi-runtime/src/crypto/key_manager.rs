@@ -1,16 +1,219 @@
+use std::collections::BTreeMap;
+
 use crate::consts::*;
 use crate::crypto::keys::{AESKey, KeyPair, Seed};
+use crate::crypto::secret_sharing::{
+    combine, onboarding_contribution, split, sum_contributions, zero_resharing_corrections, Share,
+    SEED_SIZE,
+};
 use crate::crypto::traits::*;
 use enclave_ffi_types::{CryptoError, EnclaveError};
 use lazy_static::lazy_static;
 use log::*;
+use sgx_trts::trts::rsgx_read_rand;
+
+/// The version assigned to the very first consensus seed, sealed at genesis.
+pub const INITIAL_CONSENSUS_SEED_VERSION: u32 = 0;
+
+/// Sealing path prefix for this enclave's own Shamir share of the consensus seed
+/// (suffixed by the share index), and for the small metadata blob recording which
+/// index/threshold this node holds so the share can be restored at boot.
+pub const CONSENSUS_SEED_SHARE_SEALING_PATH: &str = "consensus_seed_share";
+pub const CONSENSUS_SEED_SHARE_META_SEALING_PATH: &str = "consensus_seed_share_meta";
+
+/// Sealing path for the persisted exchange-keypair algorithm tags, so a node can
+/// recover a non-default curve selection across restarts.
+pub const CONSENSUS_EXCHANGE_ALGORITHM_SEALING_PATH: &str = "consensus_exchange_algorithm";
+
+/// Read `len` bytes from the enclave RNG, surfacing a transient failure as an
+/// error instead of panicking the enclave.
+fn read_enclave_rand(len: usize) -> Result<Vec<u8>, CryptoError> {
+    let mut buf = vec![0u8; len];
+    rsgx_read_rand(&mut buf).map_err(|e| {
+        error!("Failed to read enclave randomness: {:?}", e);
+        CryptoError::KeyError
+    })?;
+    Ok(buf)
+}
+
+/// The signature / key-exchange algorithm backing a `KeyPair`. Historically the
+/// enclave hard-wired secp256k1.
+///
+/// This enum, its on-disk tags and the per-version plumbing below are the
+/// *scaffolding* for pluggable curves: they thread an algorithm selection
+/// through configuration, sealing and derivation so that adding a curve becomes
+/// a localized change. The selection is deliberately inert until `KeyPair` gains
+/// curve-specific construction (`KeyPair::new_from_slice_with_algorithm`) in the
+/// `keys` module — until then derivation only honours secp256k1 and every other
+/// variant is refused (see [`KeyAlgorithm::is_supported`]) so a sealed tag can
+/// never disagree with the keypair actually produced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    Secp256k1,
+    Ed25519,
+    P256,
+}
+
+impl Default for KeyAlgorithm {
+    fn default() -> Self {
+        // secp256k1 is the original, pre-abstraction curve and stays the default
+        // so that nodes sealed before this change keep deriving the same keys.
+        KeyAlgorithm::Secp256k1
+    }
+}
+
+impl KeyAlgorithm {
+    /// Whether key derivation actually implements this algorithm. Only secp256k1
+    /// is wired into derivation today; the other variants are scaffolding,
+    /// reserved for when `KeyPair` gains curve-specific construction
+    /// (`new_from_slice_with_algorithm`). We refuse to record or derive under an
+    /// algorithm the crypto does not yet honour, rather than silently producing a
+    /// secp256k1 keypair behind a mismatched tag.
+    fn is_supported(self) -> bool {
+        matches!(self, KeyAlgorithm::Secp256k1)
+    }
+
+    /// Stable on-disk tag recorded in the sealed blob so a non-default curve can
+    /// be recovered across restarts. The `Secp256k1` tag is `0`, keeping the
+    /// all-zero default consistent with pre-versioning nodes.
+    fn to_tag(self) -> u8 {
+        match self {
+            KeyAlgorithm::Secp256k1 => 0,
+            KeyAlgorithm::Ed25519 => 1,
+            KeyAlgorithm::P256 => 2,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Self {
+        match tag {
+            1 => KeyAlgorithm::Ed25519,
+            2 => KeyAlgorithm::P256,
+            _ => KeyAlgorithm::Secp256k1,
+        }
+    }
+}
+
+/// The full set of keys derived from a single `consensus_seed` version, together
+/// with the algorithm each exchange keypair was derived under.
+#[derive(Clone)]
+struct ConsensusKeys {
+    state_ikm: AESKey,
+    seed_exchange_keypair: KeyPair,
+    seed_exchange_algorithm: KeyAlgorithm,
+    io_exchange_keypair: KeyPair,
+    io_exchange_algorithm: KeyAlgorithm,
+}
+
+/// The outcome of attempting to unseal a single key at boot. Distinguishes a
+/// fresh node (no sealed blob yet) from a present-but-unreadable blob, which on
+/// an SGX keystore usually means corruption, an MRENCLAVE mismatch or tampering
+/// — a condition a node operator may want to refuse to boot on.
+#[derive(Debug)]
+pub enum KeyStatus {
+    Missing,
+    Corrupted(EnclaveError),
+    Loaded,
+}
+
+impl KeyStatus {
+    pub fn is_corrupted(&self) -> bool {
+        matches!(self, KeyStatus::Corrupted(_))
+    }
+}
+
+/// Per-key report produced by [`Keychain::load`], so callers can fail closed when
+/// a sealed seed is present but cannot be unsealed instead of silently treating
+/// it as a fresh node.
+#[derive(Debug)]
+pub struct KeychainLoadReport {
+    pub consensus_seed: KeyStatus,
+    pub registration_key: KeyStatus,
+}
+
+impl KeychainLoadReport {
+    /// Whether any tracked key was present but unreadable.
+    pub fn is_corrupted(&self) -> bool {
+        self.consensus_seed.is_corrupted() || self.registration_key.is_corrupted()
+    }
+}
 
 pub struct Keychain {
+    // All known consensus seeds, keyed by their version. `consensus_seed` and the
+    // `consensus_*` fields below always mirror the `current_version` entry so that
+    // existing callers keep observing the seed currently used for new encryption.
+    consensus_seeds: BTreeMap<u32, Seed>,
+    consensus_keys: BTreeMap<u32, ConsensusKeys>,
+    current_version: u32,
     consensus_seed: Option<Seed>,
     consensus_state_ikm: Option<AESKey>,
     consensus_seed_exchange_keypair: Option<KeyPair>,
     consensus_io_exchange_keypair: Option<KeyPair>,
     registration_key: Option<KeyPair>,
+    // This enclave's own Shamir share of the consensus seed (when the seed is
+    // threshold-shared rather than held in full), plus the reconstruction
+    // threshold `k` agreed by the cluster.
+    consensus_seed_share: Option<Share>,
+    share_threshold: Option<u8>,
+    // The algorithm each exchange keypair is derived under; the IO and seed
+    // exchange keypairs may use different curves.
+    consensus_seed_exchange_algorithm: KeyAlgorithm,
+    consensus_io_exchange_algorithm: KeyAlgorithm,
+}
+
+/// The sealing path under which the seed of a given version is stored. Version 0
+/// keeps the historical un-suffixed path so that nodes sealed before versioning
+/// can still unseal their seed.
+fn consensus_seed_sealing_path(version: u32) -> String {
+    if version == INITIAL_CONSENSUS_SEED_VERSION {
+        CONSENSUS_SEED_SEALING_PATH.to_string()
+    } else {
+        format!("{}.{}", CONSENSUS_SEED_SEALING_PATH, version)
+    }
+}
+
+/// The result of probing a single seed version during the boot-time version walk.
+enum SeedProbe {
+    Loaded,
+    Missing,
+    Corrupted(EnclaveError),
+}
+
+/// Classify a failed unseal: a present-but-unreadable blob is corruption, an
+/// absent one is simply a fresh key.
+fn classify_missing_or_corrupted(exists: bool, err: EnclaveError) -> KeyStatus {
+    if exists {
+        KeyStatus::Corrupted(err)
+    } else {
+        KeyStatus::Missing
+    }
+}
+
+/// Walk consecutive seed versions starting from genesis, stopping at the first
+/// version that is missing (end of the chain) or corrupted. Returns the highest
+/// loaded version and the status of the version that halted the walk (`Loaded`
+/// if at least one version loaded and the chain simply ended).
+fn walk_seed_versions<F>(mut probe: F) -> (u32, KeyStatus)
+where
+    F: FnMut(u32) -> SeedProbe,
+{
+    let mut current_version = INITIAL_CONSENSUS_SEED_VERSION;
+    let mut status = KeyStatus::Missing;
+    let mut version = INITIAL_CONSENSUS_SEED_VERSION;
+    loop {
+        match probe(version) {
+            SeedProbe::Loaded => {
+                current_version = version;
+                status = KeyStatus::Loaded;
+                version += 1;
+            }
+            SeedProbe::Missing => break,
+            SeedProbe::Corrupted(e) => {
+                status = KeyStatus::Corrupted(e);
+                break;
+            }
+        }
+    }
+    (current_version, status)
 }
 
 lazy_static! {
@@ -18,28 +221,107 @@ lazy_static! {
 }
 
 impl Keychain {
-    pub fn new() -> Self {
-        let consensus_seed = match Seed::unseal(CONSENSUS_SEED_SEALING_PATH) {
-            Ok(k) => Some(k),
-            Err(e) => None,
-        };
+    /// Construct a keychain, reporting per key whether its sealed blob was
+    /// missing (fresh node), loaded, or present-but-corrupted. Corruption is
+    /// surfaced rather than discarded so the caller can refuse to boot.
+    pub fn load() -> (Self, KeychainLoadReport) {
+        // Unseal every seed version we can find, starting from the genesis seed and
+        // walking forward until a version is missing. The highest readable version
+        // becomes the current one used for new encryption. A present-but-unreadable
+        // version halts the walk and is reported as corruption.
+        let mut consensus_seeds = BTreeMap::new();
+        let (current_version, consensus_seed_status) = walk_seed_versions(|version| {
+            let path = consensus_seed_sealing_path(version);
+            match Seed::unseal(&path) {
+                Ok(seed) => {
+                    consensus_seeds.insert(version, seed);
+                    SeedProbe::Loaded
+                }
+                Err(e) => {
+                    match classify_missing_or_corrupted(Self::sealed_blob_exists(&path), e) {
+                        KeyStatus::Corrupted(e) => {
+                            error!(
+                                "consensus_seed at {} is present but could not be unsealed: {:?}",
+                                path, e
+                            );
+                            SeedProbe::Corrupted(e)
+                        }
+                        _ => SeedProbe::Missing,
+                    }
+                }
+            }
+        });
 
-        let registration_key = match KeyPair::unseal(REGISTRATION_KEY_SEALING_PATH) {
-            Ok(k) => Some(k),
-            Err(e) => None,
-        };
+        let consensus_seed = consensus_seeds.get(&current_version).cloned();
+
+        let (registration_key, registration_key_status) =
+            match KeyPair::unseal(REGISTRATION_KEY_SEALING_PATH) {
+                Ok(k) => (Some(k), KeyStatus::Loaded),
+                Err(e) => {
+                    let status = classify_missing_or_corrupted(
+                        Self::sealed_blob_exists(REGISTRATION_KEY_SEALING_PATH),
+                        e,
+                    );
+                    if let KeyStatus::Corrupted(ref ce) = status {
+                        error!(
+                            "registration_key at {} is present but could not be unsealed: {:?}",
+                            REGISTRATION_KEY_SEALING_PATH, ce
+                        );
+                    }
+                    (None, status)
+                }
+            };
 
         let mut x = Keychain {
+            consensus_seeds,
+            consensus_keys: BTreeMap::new(),
+            current_version,
             consensus_seed,
             registration_key,
             consensus_state_ikm: None,
             consensus_seed_exchange_keypair: None,
             consensus_io_exchange_keypair: None,
+            consensus_seed_share: None,
+            share_threshold: None,
+            consensus_seed_exchange_algorithm: KeyAlgorithm::default(),
+            consensus_io_exchange_algorithm: KeyAlgorithm::default(),
         };
 
+        // Restore this enclave's own sealed share of the seed (if it holds one),
+        // so the threshold read path survives a restart.
+        if let Some((share, k)) = Self::unseal_consensus_seed_share() {
+            x.consensus_seed_share = Some(share);
+            x.share_threshold = Some(k);
+        }
+
+        // Restore any persisted non-default exchange-keypair algorithm selection
+        // before deriving, so recovered curves match what was sealed.
+        let (seed_algo, io_algo) = Self::unseal_exchange_algorithms();
+        x.consensus_seed_exchange_algorithm = seed_algo;
+        x.consensus_io_exchange_algorithm = io_algo;
+
         x.generate_consensus_master_keys();
 
-        return x;
+        let report = KeychainLoadReport {
+            consensus_seed: consensus_seed_status,
+            registration_key: registration_key_status,
+        };
+
+        (x, report)
+    }
+
+    /// Backwards-compatible constructor that discards the load report. New callers
+    /// that need to fail closed on corruption should use [`Keychain::load`].
+    pub fn new() -> Self {
+        let (keychain, _report) = Self::load();
+        keychain
+    }
+
+    /// Whether a sealed blob physically exists at `path`. Used to tell a fresh
+    /// node (no file) apart from a corrupted / tampered sealed blob (file present
+    /// but unsealing failed).
+    fn sealed_blob_exists(path: &str) -> bool {
+        std::untrusted::fs::metadata(path).is_ok()
     }
 
     pub fn create_consensus_seed(&mut self) -> Result<(), CryptoError> {
@@ -150,53 +432,381 @@ impl Keychain {
         self.consensus_io_exchange_keypair = Some(kp.clone())
     }
 
+    /// Select the curves for the consensus seed- and IO-exchange keypairs (e.g.
+    /// from registration attestation), persist the choice so it survives restarts,
+    /// and re-derive the master key set under the new algorithms.
+    ///
+    /// Scaffolding caveat: until `KeyPair` gains curve-specific construction this
+    /// only succeeds for the default secp256k1 curve; any other selection is
+    /// refused below rather than sealed behind a keypair the derivation cannot
+    /// honour.
+    pub fn configure_consensus_exchange_algorithms(
+        &mut self,
+        seed_algo: KeyAlgorithm,
+        io_algo: KeyAlgorithm,
+    ) -> Result<(), EnclaveError> {
+        // Refuse to persist a curve the derivation does not implement, so a sealed
+        // tag can never disagree with the keypair actually produced.
+        for algo in [seed_algo, io_algo] {
+            if !algo.is_supported() {
+                error!(
+                    "Key algorithm {:?} is not implemented in key derivation; refusing to configure it",
+                    algo
+                );
+                return Err(EnclaveError::FailedSeed);
+            }
+        }
+        let mut tags = [0u8; SEED_SIZE];
+        tags[0] = seed_algo.to_tag();
+        tags[1] = io_algo.to_tag();
+        if let Err(e) = Seed::new_from_slice(&tags).seal(CONSENSUS_EXCHANGE_ALGORITHM_SEALING_PATH) {
+            error!("Error sealing consensus exchange algorithm selection");
+            return Err(e);
+        }
+        self.consensus_seed_exchange_algorithm = seed_algo;
+        self.consensus_io_exchange_algorithm = io_algo;
+        self.generate_consensus_master_keys()
+    }
+
+    pub fn get_consensus_seed_exchange_algorithm(&self) -> KeyAlgorithm {
+        self.consensus_seed_exchange_algorithm
+    }
+
+    pub fn get_consensus_io_exchange_algorithm(&self) -> KeyAlgorithm {
+        self.consensus_io_exchange_algorithm
+    }
+
+    /// Restore the persisted exchange-keypair algorithm selection, defaulting to
+    /// secp256k1 for both when nothing was sealed (fresh or pre-abstraction node).
+    fn unseal_exchange_algorithms() -> (KeyAlgorithm, KeyAlgorithm) {
+        match Seed::unseal(CONSENSUS_EXCHANGE_ALGORITHM_SEALING_PATH) {
+            Ok(seed) => {
+                let bytes = seed.get();
+                (
+                    KeyAlgorithm::from_tag(bytes[0]),
+                    KeyAlgorithm::from_tag(bytes[1]),
+                )
+            }
+            Err(_) => (KeyAlgorithm::default(), KeyAlgorithm::default()),
+        }
+    }
+
     pub fn set_consensus_state_ikm(&mut self, consensus_state_ikm: AESKey) {
         self.consensus_state_ikm = Some(consensus_state_ikm.clone());
     }
 
     pub fn set_consensus_seed(&mut self, consensus_seed: Seed) -> Result<(), EnclaveError> {
-        if let Err(e) = consensus_seed.seal(CONSENSUS_SEED_SEALING_PATH) {
+        let version = self.current_version;
+        if let Err(e) = consensus_seed.seal(&consensus_seed_sealing_path(version)) {
             error!("Error sealing consensus_seed");
             return Err(e);
         }
+        self.consensus_seeds.insert(version, consensus_seed.clone());
         Ok(self.consensus_seed = Some(consensus_seed.clone()))
     }
 
+    pub fn get_current_consensus_seed_version(&self) -> u32 {
+        self.current_version
+    }
+
+    /// Shamir-split the current `consensus_seed` into `n` shares, any `k` of which
+    /// can later reconstruct it. The seed itself is never altered; this only
+    /// produces shares to be distributed (and sealed) across registered enclaves.
+    pub fn split_consensus_seed(&self, k: u8, n: u8) -> Result<Vec<Share>, CryptoError> {
+        let seed = self.get_consensus_seed()?;
+        let mut secret = [0u8; SEED_SIZE];
+        secret.copy_from_slice(seed.get());
+        let random_bytes = read_enclave_rand(SEED_SIZE * (k.saturating_sub(1) as usize))?;
+        split(&secret, k, n, |_len| random_bytes.clone())
+    }
+
+    /// The sealing path under which this enclave stores its own share (suffixed
+    /// by the share index so a node can hold at most one share per index).
+    fn consensus_seed_share_sealing_path(index: u8) -> String {
+        format!("{}.{}", CONSENSUS_SEED_SHARE_SEALING_PATH, index)
+    }
+
+    /// Seal and store this enclave's own share of the consensus seed, together
+    /// with the reconstruction threshold `k` agreed by the cluster.
+    pub fn set_consensus_seed_share(
+        &mut self,
+        share: Share,
+        k: u8,
+    ) -> Result<(), EnclaveError> {
+        // Seal the share bytes themselves, then a tiny metadata blob recording the
+        // index and threshold so the share can be located and restored at boot.
+        let seed = Seed::new_from_slice(&share.bytes);
+        if let Err(e) = seed.seal(&Self::consensus_seed_share_sealing_path(share.index)) {
+            error!("Error sealing consensus_seed share");
+            return Err(e);
+        }
+        let mut meta = [0u8; SEED_SIZE];
+        meta[0] = share.index;
+        meta[1] = k;
+        if let Err(e) = Seed::new_from_slice(&meta).seal(CONSENSUS_SEED_SHARE_META_SEALING_PATH) {
+            error!("Error sealing consensus_seed share metadata");
+            return Err(e);
+        }
+        self.consensus_seed_share = Some(share);
+        self.share_threshold = Some(k);
+        Ok(())
+    }
+
+    /// Restore this enclave's sealed share (if any) at boot, via the metadata blob
+    /// that records its index and threshold. A missing metadata blob simply means
+    /// this node does not hold a share.
+    fn unseal_consensus_seed_share() -> Option<(Share, u8)> {
+        let meta = Seed::unseal(CONSENSUS_SEED_SHARE_META_SEALING_PATH).ok()?;
+        let meta_bytes = meta.get();
+        let index = meta_bytes[0];
+        let k = meta_bytes[1];
+        let share_seed = Seed::unseal(&Self::consensus_seed_share_sealing_path(index)).ok()?;
+        let mut bytes = [0u8; SEED_SIZE];
+        bytes.copy_from_slice(share_seed.get());
+        Some((Share { index, bytes }, k))
+    }
+
+    pub fn get_consensus_seed_share(&self) -> Result<Share, CryptoError> {
+        self.consensus_seed_share.ok_or_else(|| {
+            error!("Error accessing consensus_seed_share (does not exist, or was not initialized)");
+            CryptoError::ParsingError
+        })
+    }
+
+    /// This enclave's Lagrange-weighted contribution to `f(new_index)`, computed
+    /// from its own share over the given quorum. Returned to the onboarding
+    /// coordinator, which sums the `k` contributions into the new holder's share
+    /// without the secret ever being reconstructed at any single node.
+    pub fn onboarding_contribution_for(
+        &self,
+        quorum_indices: &[u8],
+        new_index: u8,
+    ) -> Result<[u8; SEED_SIZE], CryptoError> {
+        let share = self.get_consensus_seed_share()?;
+        onboarding_contribution(&share, quorum_indices, new_index)
+    }
+
+    /// Combine the Lagrange-weighted contributions collected from a quorum of `k`
+    /// existing shareholders into a fresh share for `new_index`, consistent with
+    /// the existing polynomial.
+    pub fn add_share_holder(
+        new_index: u8,
+        contributions: &[[u8; SEED_SIZE]],
+    ) -> Result<Share, CryptoError> {
+        if contributions.is_empty() {
+            error!("Cannot onboard a holder without any contributions");
+            return Err(CryptoError::KeyError);
+        }
+        Ok(Share {
+            index: new_index,
+            bytes: sum_contributions(contributions),
+        })
+    }
+
+    /// Begin removing a holder by proactively re-sharing: emit this enclave's
+    /// round of zero-constant corrections for every index in `surviving_indices`.
+    /// The removed holder is the one whose index is left out of that list.
+    /// The reconstructed secret is unchanged, but once each survivor folds in the
+    /// corrections it receives (via [`apply_resharing_corrections`]) the removed
+    /// node's old share no longer lies on the active polynomial.
+    pub fn remove_share_holder(
+        &self,
+        surviving_indices: &[u8],
+    ) -> Result<Vec<Share>, CryptoError> {
+        let k = self.share_threshold.ok_or_else(|| {
+            error!("Share threshold not set; cannot re-share");
+            CryptoError::KeyError
+        })?;
+        let random_bytes = read_enclave_rand(SEED_SIZE * (k.saturating_sub(1) as usize))?;
+        zero_resharing_corrections(surviving_indices, k, |_len| random_bytes.clone())
+    }
+
+    /// Fold a set of re-sharing corrections addressed to this enclave's share into
+    /// its own share, re-sealing the result.
+    pub fn apply_resharing_corrections(
+        &mut self,
+        corrections: &[[u8; SEED_SIZE]],
+    ) -> Result<(), EnclaveError> {
+        let mut share = self.get_consensus_seed_share().map_err(|err| {
+            error!("[Enclave] No share to apply corrections to: {:?}", err);
+            EnclaveError::FailedSeed
+        })?;
+        // Refuse to re-seal with a bogus threshold: a missing threshold means this
+        // node was never told the cluster's `k`, so clobbering it would corrupt
+        // shared state.
+        let k = self.share_threshold.ok_or_else(|| {
+            error!("Share threshold not set; cannot apply re-sharing corrections");
+            EnclaveError::FailedSeed
+        })?;
+        let mut inputs = Vec::with_capacity(corrections.len() + 1);
+        inputs.push(share.bytes);
+        inputs.extend_from_slice(corrections);
+        share.bytes = sum_contributions(&inputs);
+        self.set_consensus_seed_share(share, k)
+    }
+
+    /// Reconstruct the consensus seed from a quorum of `k` shares and install it
+    /// as the current seed, deriving the full master key set from it. The
+    /// threshold `k` is supplied by the caller rather than read from local state,
+    /// so a recovery/coordinator enclave that collects shares without itself
+    /// holding one can still reconstruct. Only called once `k` shares have been
+    /// collected from cooperating enclaves.
+    pub fn reconstruct_consensus_seed(
+        &mut self,
+        shares: &[Share],
+        k: u8,
+    ) -> Result<(), EnclaveError> {
+        // Enforce the quorum: interpolating fewer than `k` shares yields a
+        // deterministic but wrong secret, which must never be installed silently.
+        if shares.len() < k as usize {
+            error!(
+                "Refusing to reconstruct consensus_seed from {} shares; threshold is {}",
+                shares.len(),
+                k
+            );
+            return Err(EnclaveError::FailedSeed);
+        }
+        let secret = combine(shares).map_err(|err| {
+            error!("[Enclave] Error reconstructing consensus_seed: {:?}", err);
+            EnclaveError::FailedSeed
+        })?;
+        let seed = Seed::new_from_slice(&secret);
+        self.set_consensus_seed(seed)?;
+        self.generate_consensus_master_keys()
+    }
+
+    pub fn get_consensus_io_exchange_keypair_for_version(
+        &self,
+        version: u32,
+    ) -> Result<KeyPair, CryptoError> {
+        match self.consensus_keys.get(&version) {
+            Some(keys) => Ok(keys.io_exchange_keypair.clone()),
+            None => {
+                error!(
+                    "Error accessing consensus_io_exchange_keypair for version {} (does not exist, or was not initialized)",
+                    version
+                );
+                Err(CryptoError::ParsingError)
+            }
+        }
+    }
+
+    /// The algorithms the seed- and IO-exchange keypairs of a given version were
+    /// derived under. Today every version derives under the single supported
+    /// curve (secp256k1); this accessor exists so callers can select the right
+    /// curve once per-algorithm derivation lands.
+    pub fn get_exchange_algorithms_for_version(
+        &self,
+        version: u32,
+    ) -> Option<(KeyAlgorithm, KeyAlgorithm)> {
+        self.consensus_keys
+            .get(&version)
+            .map(|keys| (keys.seed_exchange_algorithm, keys.io_exchange_algorithm))
+    }
+
+    pub fn get_consensus_state_ikm_for_version(
+        &self,
+        version: u32,
+    ) -> Result<AESKey, CryptoError> {
+        match self.consensus_keys.get(&version) {
+            Some(keys) => Ok(keys.state_ikm),
+            None => {
+                error!(
+                    "Error accessing consensus_state_ikm for version {} (does not exist, or was not initialized)",
+                    version
+                );
+                Err(CryptoError::ParsingError)
+            }
+        }
+    }
+
+    /// Generate a fresh `Seed`, bump the current version, derive a new master key
+    /// set for it and seal it, while keeping every older version available so that
+    /// ciphertext sealed by a previous seed remains decryptable.
+    pub fn rotate_consensus_seed(&mut self) -> Result<(), EnclaveError> {
+        let new_seed = Seed::new().map_err(|err| {
+            error!("[Enclave] Error generating rotated consensus_seed: {:?}", err);
+            EnclaveError::FailedSeed
+        })?;
+        self.current_version += 1;
+        if let Err(e) = self.set_consensus_seed(new_seed) {
+            // roll back the version bump so the keychain stays consistent
+            self.current_version -= 1;
+            return Err(e);
+        }
+        self.generate_consensus_master_keys()
+    }
+
     pub fn generate_consensus_master_keys(&mut self) -> Result<(), EnclaveError> {
         if !self.is_consensus_seed_set() {
             debug!("Seed not initialized! Cannot derive enclave keys");
             return Ok(());
         }
 
-        // consensus_seed_exchange_keypair
+        // Derive the full master key set for every known seed version, so that
+        // ciphertext tagged with an older version can still be decrypted.
+        let versions: Vec<u32> = self.consensus_seeds.keys().cloned().collect();
+        for version in versions {
+            let keys = self.derive_consensus_keys_for_version(version)?;
+            self.consensus_keys.insert(version, keys);
+        }
 
-        let consensus_seed_exchange_keypair_bytes = self
-            .consensus_seed
-            .unwrap()
-            .derive_key_from_this(&CONSENSUS_SEED_EXCHANGE_KEYPAIR_DERIVE_ORDER.to_be_bytes());
-        let consensus_seed_exchange_keypair = KeyPair::new_from_slice(
-            &consensus_seed_exchange_keypair_bytes.get(),
-        )
-        .map_err(|err| {
-            error!(
-                "[Enclave] Error creating consensus_seed_exchange_keypair: {:?}",
-                err
-            );
+        // Mirror the current version into the flat fields used by legacy callers,
+        // which always operate on the seed used for new encryption.
+        if let Some(keys) = self.consensus_keys.get(&self.current_version).cloned() {
+            self.set_consensus_seed_exchange_keypair(keys.seed_exchange_keypair);
+            self.set_consensus_io_exchange_keypair(keys.io_exchange_keypair);
+            self.set_consensus_state_ikm(keys.state_ikm);
+        }
+
+        Ok(())
+    }
+
+    fn derive_consensus_keys_for_version(
+        &self,
+        version: u32,
+    ) -> Result<ConsensusKeys, EnclaveError> {
+        let seed = self.consensus_seeds.get(&version).cloned().ok_or_else(|| {
+            error!("[Enclave] No consensus_seed for version {}", version);
             EnclaveError::FailedUnseal /* change error type? */
         })?;
+
+        // consensus_seed_exchange_keypair
+
+        let seed_exchange_algorithm = self.consensus_seed_exchange_algorithm;
+        let io_exchange_algorithm = self.consensus_io_exchange_algorithm;
+        // Fail closed: never derive a secp256k1 keypair while claiming a different
+        // curve. Once `KeyPair` gains curve-specific construction this is where it
+        // branches per algorithm.
+        for algo in [seed_exchange_algorithm, io_exchange_algorithm] {
+            if !algo.is_supported() {
+                error!(
+                    "[Enclave] Key algorithm {:?} is recorded but not implemented in derivation",
+                    algo
+                );
+                return Err(EnclaveError::FailedSeed);
+            }
+        }
+        let consensus_seed_exchange_keypair_bytes =
+            seed.derive_key_from_this(&CONSENSUS_SEED_EXCHANGE_KEYPAIR_DERIVE_ORDER.to_be_bytes());
+        let seed_exchange_keypair =
+            KeyPair::new_from_slice(&consensus_seed_exchange_keypair_bytes.get()).map_err(|err| {
+                error!(
+                    "[Enclave] Error creating consensus_seed_exchange_keypair: {:?}",
+                    err
+                );
+                EnclaveError::FailedUnseal /* change error type? */
+            })?;
         info!(
-            "consensus_seed_exchange_keypair: {:?}",
-            consensus_seed_exchange_keypair
+            "consensus_seed_exchange_keypair (v{}): {:?}",
+            version, seed_exchange_keypair
         );
-        self.set_consensus_seed_exchange_keypair(consensus_seed_exchange_keypair);
 
         // consensus_io_exchange_keypair
 
-        let consensus_io_exchange_keypair_bytes = self
-            .consensus_seed
-            .unwrap()
-            .derive_key_from_this(&CONSENSUS_IO_EXCHANGE_KEYPAIR_DERIVE_ORDER.to_be_bytes());
-        let consensus_io_exchange_keypair =
+        let consensus_io_exchange_keypair_bytes =
+            seed.derive_key_from_this(&CONSENSUS_IO_EXCHANGE_KEYPAIR_DERIVE_ORDER.to_be_bytes());
+        let io_exchange_keypair =
             KeyPair::new_from_slice(&consensus_io_exchange_keypair_bytes.get()).map_err(|err| {
                 error!(
                     "[Enclave] Error creating consensus_io_exchange_keypair: {:?}",
@@ -205,22 +815,125 @@ impl Keychain {
                 EnclaveError::FailedUnseal /* change error type? */
             })?;
         info!(
-            "consensus_io_exchange_keypair: {:?}",
-            consensus_io_exchange_keypair
+            "consensus_io_exchange_keypair (v{}): {:?}",
+            version, io_exchange_keypair
         );
-        self.set_consensus_io_exchange_keypair(consensus_io_exchange_keypair);
 
         // consensus_state_ikm
 
-        let consensus_state_ikm_bytes = self
-            .consensus_seed
-            .unwrap()
-            .derive_key_from_this(&CONSENSUS_STATE_IKM_DERIVE_ORDER.to_be_bytes());
-        let consensus_state_ikm = AESKey::new_from_slice(consensus_state_ikm_bytes.get());
-        info!("consensus_state_ikm: {:?}", consensus_state_ikm);
-        self.set_consensus_state_ikm(consensus_state_ikm);
+        let consensus_state_ikm_bytes =
+            seed.derive_key_from_this(&CONSENSUS_STATE_IKM_DERIVE_ORDER.to_be_bytes());
+        let state_ikm = AESKey::new_from_slice(consensus_state_ikm_bytes.get());
+        info!("consensus_state_ikm (v{}): {:?}", version, state_ikm);
 
-        Ok(())
+        Ok(ConsensusKeys {
+            state_ikm,
+            seed_exchange_keypair,
+            seed_exchange_algorithm,
+            io_exchange_keypair,
+            io_exchange_algorithm,
+        })
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn genesis_seed_keeps_legacy_sealing_path() {
+        // Version 0 must keep the historical un-suffixed path so that nodes sealed
+        // before versioning can still unseal their seed.
+        assert_eq!(
+            consensus_seed_sealing_path(INITIAL_CONSENSUS_SEED_VERSION),
+            CONSENSUS_SEED_SEALING_PATH.to_string()
+        );
+    }
+
+    #[test]
+    fn rotated_seed_versions_get_distinct_suffixed_paths() {
+        let v1 = consensus_seed_sealing_path(1);
+        let v2 = consensus_seed_sealing_path(2);
+        assert_eq!(v1, format!("{}.1", CONSENSUS_SEED_SEALING_PATH));
+        assert_eq!(v2, format!("{}.2", CONSENSUS_SEED_SEALING_PATH));
+        assert_ne!(v1, v2);
+    }
+
+    #[test]
+    fn key_algorithm_defaults_to_secp256k1() {
+        assert_eq!(KeyAlgorithm::default(), KeyAlgorithm::Secp256k1);
+        assert_eq!(KeyAlgorithm::Secp256k1.to_tag(), 0);
+    }
+
+    #[test]
+    fn key_algorithm_tag_round_trips_for_every_variant() {
+        for algo in [
+            KeyAlgorithm::Secp256k1,
+            KeyAlgorithm::Ed25519,
+            KeyAlgorithm::P256,
+        ] {
+            assert_eq!(KeyAlgorithm::from_tag(algo.to_tag()), algo);
+        }
+    }
+
+    #[test]
+    fn unknown_algorithm_tag_falls_back_to_default() {
+        assert_eq!(KeyAlgorithm::from_tag(0xff), KeyAlgorithm::Secp256k1);
+    }
+
+    #[test]
+    fn only_secp256k1_is_currently_supported_for_derivation() {
+        assert!(KeyAlgorithm::Secp256k1.is_supported());
+        assert!(!KeyAlgorithm::Ed25519.is_supported());
+        assert!(!KeyAlgorithm::P256.is_supported());
+    }
+
+    #[test]
+    fn absent_blob_is_missing_present_blob_is_corrupted() {
+        assert!(matches!(
+            classify_missing_or_corrupted(false, EnclaveError::FailedUnseal),
+            KeyStatus::Missing
+        ));
+        assert!(classify_missing_or_corrupted(true, EnclaveError::FailedUnseal).is_corrupted());
+    }
+
+    #[test]
+    fn fresh_node_with_no_seed_reports_missing() {
+        let (version, status) = walk_seed_versions(|_| SeedProbe::Missing);
+        assert_eq!(version, INITIAL_CONSENSUS_SEED_VERSION);
+        assert!(matches!(status, KeyStatus::Missing));
+    }
+
+    #[test]
+    fn walk_stops_at_end_of_loaded_chain() {
+        // versions 0 and 1 present, 2 missing -> current is 1, chain ended cleanly
+        let (version, status) = walk_seed_versions(|v| {
+            if v < 2 {
+                SeedProbe::Loaded
+            } else {
+                SeedProbe::Missing
+            }
+        });
+        assert_eq!(version, 1);
+        assert!(matches!(status, KeyStatus::Loaded));
+    }
+
+    #[test]
+    fn walk_surfaces_corruption_mid_chain() {
+        // version 0 loads, version 1 is present but unreadable -> corruption wins
+        let (version, status) = walk_seed_versions(|v| match v {
+            0 => SeedProbe::Loaded,
+            _ => SeedProbe::Corrupted(EnclaveError::FailedUnseal),
+        });
+        assert_eq!(version, 0);
+        assert!(status.is_corrupted());
+    }
+
+    #[test]
+    fn corruption_at_genesis_is_reported() {
+        let (_version, status) =
+            walk_seed_versions(|_| SeedProbe::Corrupted(EnclaveError::FailedUnseal));
+        assert!(status.is_corrupted());
     }
 }
 
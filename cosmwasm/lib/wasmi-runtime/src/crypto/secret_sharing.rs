@@ -0,0 +1,321 @@
+use enclave_ffi_types::CryptoError;
+use log::*;
+
+/// The size, in bytes, of a consensus seed and therefore of every share of it.
+pub const SEED_SIZE: usize = 32;
+
+/// A single Shamir share of the consensus seed. `index` is the non-zero x
+/// coordinate at which the secret-sharing polynomial was evaluated, and `bytes`
+/// holds the per-byte evaluations (the seed is shared byte-wise over GF(256)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Share {
+    pub index: u8,
+    pub bytes: [u8; SEED_SIZE],
+}
+
+/// Multiply two elements of GF(256) using the AES field (reducing polynomial
+/// x^8 + x^4 + x^3 + x + 1, i.e. 0x1b once the high bit overflows).
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product: u8 = 0;
+    for _ in 0..8 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let high_bit_set = a & 0x80 != 0;
+        a <<= 1;
+        if high_bit_set {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(256), computed as a^254 (since a^255 == 1 for
+/// every non-zero element). The inverse of 0 is undefined and returns 0.
+fn gf_inv(a: u8) -> u8 {
+    let mut result: u8 = 1;
+    // a^254 = a^(2+4+8+16+32+64+128)
+    let mut power = a;
+    for _ in 0..7 {
+        power = gf_mul(power, power);
+        result = gf_mul(result, power);
+    }
+    result
+}
+
+fn gf_div(a: u8, b: u8) -> u8 {
+    gf_mul(a, gf_inv(b))
+}
+
+/// Evaluate the polynomial with the given coefficients (constant term first) at
+/// `x` over GF(256) using Horner's method.
+fn gf_eval(coeffs: &[u8], x: u8) -> u8 {
+    let mut acc: u8 = 0;
+    for &coeff in coeffs.iter().rev() {
+        acc = gf_mul(acc, x) ^ coeff;
+    }
+    acc
+}
+
+/// Split a 32-byte secret into `n` Shamir shares such that any `k` of them can
+/// reconstruct it. The random polynomial coefficients are supplied by `rand` (a
+/// closure returning `SEED_SIZE * (k - 1)` random bytes) so that callers control
+/// the entropy source (the enclave RNG).
+pub fn split<R>(secret: &[u8; SEED_SIZE], k: u8, n: u8, mut rand: R) -> Result<Vec<Share>, CryptoError>
+where
+    R: FnMut(usize) -> Vec<u8>,
+{
+    if k == 0 || n == 0 || k > n {
+        error!("Invalid threshold parameters: k={}, n={}", k, n);
+        return Err(CryptoError::KeyError);
+    }
+
+    let degree = (k - 1) as usize;
+    let random_bytes = rand(SEED_SIZE * degree);
+    if random_bytes.len() < SEED_SIZE * degree {
+        error!("Not enough randomness to build sharing polynomials");
+        return Err(CryptoError::KeyError);
+    }
+
+    let mut shares = Vec::with_capacity(n as usize);
+    for x in 1..=n {
+        let mut bytes = [0u8; SEED_SIZE];
+        for (byte_idx, out) in bytes.iter_mut().enumerate() {
+            // Build the per-byte polynomial: constant term is the secret byte,
+            // the remaining `degree` coefficients come from the RNG stream.
+            let mut coeffs = Vec::with_capacity(k as usize);
+            coeffs.push(secret[byte_idx]);
+            for d in 0..degree {
+                coeffs.push(random_bytes[d * SEED_SIZE + byte_idx]);
+            }
+            *out = gf_eval(&coeffs, x);
+        }
+        shares.push(Share { index: x, bytes });
+    }
+
+    Ok(shares)
+}
+
+/// Reconstruct the secret from at least `k` shares via Lagrange interpolation at
+/// x = 0 over GF(256). Rejects shares with a zero index (x = 0 is the secret
+/// itself) or duplicate indices.
+pub fn combine(shares: &[Share]) -> Result<[u8; SEED_SIZE], CryptoError> {
+    if shares.is_empty() {
+        error!("Cannot reconstruct secret from zero shares");
+        return Err(CryptoError::KeyError);
+    }
+
+    let mut seen = Vec::with_capacity(shares.len());
+    for share in shares {
+        if share.index == 0 {
+            error!("Share with index 0 is not a valid evaluation point");
+            return Err(CryptoError::KeyError);
+        }
+        if seen.contains(&share.index) {
+            error!("Duplicate share index {}", share.index);
+            return Err(CryptoError::KeyError);
+        }
+        seen.push(share.index);
+    }
+
+    let mut secret = [0u8; SEED_SIZE];
+    for (byte_idx, out) in secret.iter_mut().enumerate() {
+        let mut acc: u8 = 0;
+        for (i, share_i) in shares.iter().enumerate() {
+            // Lagrange basis polynomial L_i evaluated at x = 0.
+            let mut numerator: u8 = 1;
+            let mut denominator: u8 = 1;
+            for (j, share_j) in shares.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                numerator = gf_mul(numerator, share_j.index);
+                denominator = gf_mul(denominator, share_i.index ^ share_j.index);
+            }
+            let lagrange = gf_div(numerator, denominator);
+            acc ^= gf_mul(share_i.bytes[byte_idx], lagrange);
+        }
+        *out = acc;
+    }
+
+    Ok(secret)
+}
+
+/// The Lagrange basis weight `L_i(x)` for the holder at `index` over the quorum
+/// `quorum_indices`, evaluated at `x` over GF(256). Used so that a set of
+/// shareholders can jointly compute `f(x)` for a fresh `x` without any single
+/// node reconstructing the secret `f(0)`.
+fn lagrange_weight(index: u8, quorum_indices: &[u8], x: u8) -> u8 {
+    let mut numerator: u8 = 1;
+    let mut denominator: u8 = 1;
+    for &other in quorum_indices {
+        if other == index {
+            continue;
+        }
+        numerator = gf_mul(numerator, x ^ other);
+        denominator = gf_mul(denominator, index ^ other);
+    }
+    gf_div(numerator, denominator)
+}
+
+/// A single holder's Lagrange-weighted contribution to `f(new_index)`: its own
+/// share scaled by its Lagrange weight over the quorum. Summing (XORing) the
+/// contributions of `k` holders yields the new share for `new_index` without the
+/// secret ever being materialized at any node.
+pub fn onboarding_contribution(
+    share: &Share,
+    quorum_indices: &[u8],
+    new_index: u8,
+) -> Result<[u8; SEED_SIZE], CryptoError> {
+    if new_index == 0 {
+        error!("Cannot onboard a holder at index 0");
+        return Err(CryptoError::KeyError);
+    }
+    let weight = lagrange_weight(share.index, quorum_indices, new_index);
+    let mut contribution = [0u8; SEED_SIZE];
+    for (out, &b) in contribution.iter_mut().zip(share.bytes.iter()) {
+        *out = gf_mul(b, weight);
+    }
+    Ok(contribution)
+}
+
+/// XOR-sum a set of GF(256) contributions of equal length. Used both to combine
+/// onboarding contributions into a new share and to fold re-sharing corrections
+/// into an existing share.
+pub fn sum_contributions(contributions: &[[u8; SEED_SIZE]]) -> [u8; SEED_SIZE] {
+    let mut acc = [0u8; SEED_SIZE];
+    for contribution in contributions {
+        for (a, &c) in acc.iter_mut().zip(contribution.iter()) {
+            *a ^= c;
+        }
+    }
+    acc
+}
+
+/// Produce a round of proactive re-sharing corrections from one holder: a fresh
+/// random degree-`(k-1)` polynomial whose constant term is **zero**, evaluated at
+/// every surviving `indices`. Because the constant term is zero the reconstructed
+/// secret is unchanged, but every holder's share is re-randomized once all holders
+/// add the corrections they receive. `rand` supplies `SEED_SIZE * (k - 1)` bytes.
+pub fn zero_resharing_corrections<R>(
+    indices: &[u8],
+    k: u8,
+    mut rand: R,
+) -> Result<Vec<Share>, CryptoError>
+where
+    R: FnMut(usize) -> Vec<u8>,
+{
+    if k == 0 {
+        return Err(CryptoError::KeyError);
+    }
+    let degree = (k - 1) as usize;
+    let random_bytes = rand(SEED_SIZE * degree);
+    if random_bytes.len() < SEED_SIZE * degree {
+        error!("Not enough randomness to build re-sharing polynomials");
+        return Err(CryptoError::KeyError);
+    }
+
+    let mut corrections = Vec::with_capacity(indices.len());
+    for &x in indices {
+        if x == 0 {
+            return Err(CryptoError::KeyError);
+        }
+        let mut bytes = [0u8; SEED_SIZE];
+        for (byte_idx, out) in bytes.iter_mut().enumerate() {
+            // constant term pinned to zero, remaining coefficients from the RNG
+            let mut coeffs = Vec::with_capacity(k as usize);
+            coeffs.push(0u8);
+            for d in 0..degree {
+                coeffs.push(random_bytes[d * SEED_SIZE + byte_idx]);
+            }
+            *out = gf_eval(&coeffs, x);
+        }
+        corrections.push(Share { index: x, bytes });
+    }
+
+    Ok(corrections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // deterministic "randomness" for reproducible test vectors
+    fn fixed_rand(n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i as u8).wrapping_mul(7).wrapping_add(1)).collect()
+    }
+
+    #[test]
+    fn test_split_and_combine_exact_threshold() {
+        let secret = [42u8; SEED_SIZE];
+        let shares = split(&secret, 3, 5, fixed_rand).unwrap();
+        let recovered = combine(&shares[0..3]).unwrap();
+        assert_eq!(secret, recovered);
+    }
+
+    #[test]
+    fn test_combine_any_k_subset() {
+        let secret = [7u8; SEED_SIZE];
+        let shares = split(&secret, 2, 4, fixed_rand).unwrap();
+        let recovered = combine(&[shares[1], shares[3]]).unwrap();
+        assert_eq!(secret, recovered);
+    }
+
+    #[test]
+    fn test_reject_duplicate_and_zero_index() {
+        let shares = [
+            Share { index: 1, bytes: [0u8; SEED_SIZE] },
+            Share { index: 1, bytes: [0u8; SEED_SIZE] },
+        ];
+        assert!(combine(&shares).is_err());
+
+        let shares = [Share { index: 0, bytes: [0u8; SEED_SIZE] }];
+        assert!(combine(&shares).is_err());
+    }
+
+    #[test]
+    fn test_onboarding_new_share_is_consistent() {
+        let secret = [99u8; SEED_SIZE];
+        let shares = split(&secret, 3, 5, fixed_rand).unwrap();
+
+        // quorum of the first 3 holders onboards a new holder at index 6
+        let quorum = &shares[0..3];
+        let quorum_indices: Vec<u8> = quorum.iter().map(|s| s.index).collect();
+        let contributions: Vec<[u8; SEED_SIZE]> = quorum
+            .iter()
+            .map(|s| onboarding_contribution(s, &quorum_indices, 6).unwrap())
+            .collect();
+        let new_bytes = sum_contributions(&contributions);
+        let new_share = Share { index: 6, bytes: new_bytes };
+
+        // the new share must combine with existing shares to the same secret
+        let recovered = combine(&[shares[3], shares[4], new_share]).unwrap();
+        assert_eq!(secret, recovered);
+    }
+
+    #[test]
+    fn test_resharing_preserves_secret_and_rerandomizes() {
+        let secret = [13u8; SEED_SIZE];
+        let mut shares = split(&secret, 2, 3, fixed_rand).unwrap();
+        let original = shares.clone();
+        let indices: Vec<u8> = shares.iter().map(|s| s.index).collect();
+
+        // two surviving holders each emit a zero-constant re-sharing round
+        let round_a = zero_resharing_corrections(&indices, 2, fixed_rand).unwrap();
+        let round_b = zero_resharing_corrections(&indices, 2, |n| {
+            (0..n).map(|i| (i as u8).wrapping_mul(11).wrapping_add(3)).collect()
+        })
+        .unwrap();
+
+        for share in shares.iter_mut() {
+            let a = round_a.iter().find(|c| c.index == share.index).unwrap().bytes;
+            let b = round_b.iter().find(|c| c.index == share.index).unwrap().bytes;
+            share.bytes = sum_contributions(&[share.bytes, a, b]);
+        }
+
+        assert_ne!(original[0].bytes, shares[0].bytes);
+        let recovered = combine(&shares[0..2]).unwrap();
+        assert_eq!(secret, recovered);
+    }
+}